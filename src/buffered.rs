@@ -0,0 +1,279 @@
+use std::{
+  cell::RefCell,
+  collections::VecDeque,
+  io::{
+    self,
+    Write,
+  },
+  rc::Rc,
+};
+use tracing_subscriber::fmt::MakeWriter;
+use wasm_bindgen::{
+  closure::Closure,
+  JsCast,
+  JsValue,
+};
+
+use crate::writer_ext::{
+  MakeConsoleWriterExt,
+  Tee,
+  TeeWriter,
+};
+
+/// A bounded, oldest-evicted ring buffer of formatted log lines,
+/// shared between every [`RingBufferWriter`] produced by a
+/// [`MakeRingBufferWriter`].
+///
+/// This mirrors the "capture the whole session to a temp file so it
+/// can be attached to a bug report" pattern native backends use, but
+/// since there's no filesystem to write to in the browser, the
+/// session is kept in memory instead and exported on demand.
+#[derive(Clone, Debug)]
+pub struct LogBuffer {
+  lines: Rc<RefCell<VecDeque<String>>>,
+  capacity: usize,
+}
+
+impl LogBuffer {
+  fn new(capacity: usize) -> Self {
+    Self {
+      lines: Rc::new(RefCell::new(VecDeque::with_capacity(capacity))),
+      capacity,
+    }
+  }
+
+  fn push(&self, line: String) {
+    if self.capacity == 0 {
+      return;
+    }
+
+    let mut lines = self.lines.borrow_mut();
+
+    if lines.len() >= self.capacity {
+      lines.pop_front();
+    }
+
+    lines.push_back(line);
+  }
+
+  /// Snapshots the currently buffered lines as a single
+  /// newline-joined string, oldest first.
+  pub fn snapshot(&self) -> String {
+    self
+      .lines
+      .borrow()
+      .iter()
+      .cloned()
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+
+  /// Triggers a browser download of [`snapshot`](Self::snapshot) as a
+  /// `.log` file named `file_name`, so a user can attach a session
+  /// log to a bug report. No-ops if `document`/`window` are
+  /// unavailable, e.g. in a worker.
+  pub fn download(&self, file_name: &str) {
+    let Some(window) = web_sys::window() else {
+      return;
+    };
+    let Some(document) = window.document() else {
+      return;
+    };
+
+    let parts = js_sys::Array::of1(&JsValue::from_str(&self.snapshot()));
+
+    let Ok(blob) = web_sys::Blob::new_with_str_sequence_and_options(
+      &parts,
+      web_sys::BlobPropertyBag::new().type_("text/plain"),
+    ) else {
+      return;
+    };
+
+    let Ok(url) = web_sys::Url::create_object_url_with_blob(&blob) else {
+      return;
+    };
+
+    if let Ok(anchor) = document.create_element("a") {
+      if let Ok(anchor) = anchor.dyn_into::<web_sys::HtmlAnchorElement>() {
+        anchor.set_href(&url);
+        anchor.set_download(file_name);
+        anchor.click();
+      }
+    }
+
+    let _ = web_sys::Url::revoke_object_url(&url);
+  }
+
+  /// Exposes [`snapshot`](Self::snapshot) as a global
+  /// `window[accessor_name]()` function, so the buffer can be dumped
+  /// from JS/DevTools at any time, e.g.
+  /// `register_global_accessor("__TRACING_LOGS__")` then
+  /// `__TRACING_LOGS__()` in the console. No-ops if `window` is
+  /// unavailable.
+  ///
+  /// The closure backing the accessor is intentionally leaked for
+  /// the lifetime of the page, since it must outlive any call made
+  /// to it from JS.
+  pub fn register_global_accessor(&self, accessor_name: &str) {
+    let Some(window) = web_sys::window() else {
+      return;
+    };
+
+    let buffer = self.clone();
+    let accessor = Closure::<dyn Fn() -> String>::new(move || buffer.snapshot());
+
+    let _ = js_sys::Reflect::set(
+      &window,
+      &JsValue::from_str(accessor_name),
+      accessor.as_ref().unchecked_ref(),
+    );
+
+    accessor.forget();
+  }
+}
+
+/// A [`MakeWriter`] whose only job is appending each formatted event
+/// to a [`LogBuffer`] — it never writes to the console itself. Paired
+/// with a real console [`MakeWriter`] via
+/// [`MakeBufferedConsoleWriter`], using [`and`](MakeConsoleWriterExt::and)
+/// under the hood, so each side's own [`Drop`] fires exactly once and
+/// nothing is ever dispatched to the console twice.
+#[derive(Clone, Debug)]
+pub struct MakeRingBufferWriter {
+  buffer: LogBuffer,
+}
+
+impl<'a> MakeWriter<'a> for MakeRingBufferWriter {
+  type Writer = RingBufferWriter;
+
+  fn make_writer(&'a self) -> Self::Writer {
+    unimplemented!("use make_writer_for instead");
+  }
+
+  fn make_writer_for(&'a self, _meta: &tracing::Metadata<'_>) -> Self::Writer {
+    RingBufferWriter {
+      buf: Vec::with_capacity(256),
+      buffer: self.buffer.clone(),
+    }
+  }
+}
+
+/// The [`MakeWriter::Writer`] produced by [`MakeRingBufferWriter`]:
+/// appends the formatted event to the shared [`LogBuffer`] on flush,
+/// without ever touching the console.
+pub struct RingBufferWriter {
+  buf: Vec<u8>,
+  buffer: LogBuffer,
+}
+
+impl io::Write for RingBufferWriter {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.buf.write(buf)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    let data = std::str::from_utf8(&self.buf).map_err(|_| {
+      io::Error::new(io::ErrorKind::InvalidData, "data not UTF-8")
+    })?;
+
+    self.buffer.push(data.to_string());
+
+    Ok(())
+  }
+}
+
+impl Drop for RingBufferWriter {
+  fn drop(&mut self) {
+    let _ = self.flush();
+  }
+}
+
+/// Like `M`, but every formatted event is also appended to a bounded
+/// in-memory [`LogBuffer`] so the session can be exported later, e.g.
+/// for a "download logs" button.
+///
+/// Built on top of [`Tee`], so the console side (`M`, typically
+/// [`MakeConsoleWriter`](crate::MakeConsoleWriter)) and the buffering
+/// side ([`MakeRingBufferWriter`]) are fully independent
+/// [`MakeWriter`]s — buffering composes with any level filtering or
+/// styling already applied to `M`, instead of re-implementing console
+/// dispatch a second time.
+#[derive(Clone, Debug)]
+pub struct MakeBufferedConsoleWriter<M = crate::MakeConsoleWriter> {
+  inner: Tee<M, MakeRingBufferWriter>,
+  buffer: LogBuffer,
+}
+
+impl MakeBufferedConsoleWriter<crate::MakeConsoleWriter> {
+  /// Creates a new [`MakeBufferedConsoleWriter`] wrapping a default
+  /// [`MakeConsoleWriter`](crate::MakeConsoleWriter), whose ring
+  /// buffer holds up to `capacity` formatted log lines, evicting the
+  /// oldest once full.
+  pub fn new(capacity: usize) -> Self {
+    Self::wrapping(crate::MakeConsoleWriter::default(), capacity)
+  }
+}
+
+impl<M> MakeBufferedConsoleWriter<M> {
+  /// Creates a new [`MakeBufferedConsoleWriter`] wrapping `make_writer`
+  /// for console output, whose ring buffer holds up to `capacity`
+  /// formatted log lines, evicting the oldest once full.
+  pub fn wrapping(make_writer: M, capacity: usize) -> Self {
+    let buffer = LogBuffer::new(capacity);
+
+    Self {
+      inner: make_writer.and(MakeRingBufferWriter {
+        buffer: buffer.clone(),
+      }),
+      buffer,
+    }
+  }
+
+  /// Returns a handle to the underlying [`LogBuffer`], so it can be
+  /// snapshotted or downloaded independently of the writer, e.g. from
+  /// a "report a bug" button elsewhere in the app.
+  pub fn buffer(&self) -> LogBuffer {
+    self.buffer.clone()
+  }
+}
+
+impl<'a, M> MakeWriter<'a> for MakeBufferedConsoleWriter<M>
+where
+  M: MakeWriter<'a>,
+{
+  type Writer = TeeWriter<M::Writer, RingBufferWriter>;
+
+  fn make_writer(&'a self) -> Self::Writer {
+    self.inner.make_writer()
+  }
+
+  fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+    self.inner.make_writer_for(meta)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn log_buffer_evicts_oldest_once_full() {
+    let buffer = LogBuffer::new(2);
+
+    buffer.push("a".to_string());
+    buffer.push("b".to_string());
+    buffer.push("c".to_string());
+
+    assert_eq!(buffer.snapshot(), "b\nc");
+  }
+
+  #[test]
+  fn log_buffer_with_zero_capacity_retains_nothing() {
+    let buffer = LogBuffer::new(0);
+
+    buffer.push("a".to_string());
+    buffer.push("b".to_string());
+
+    assert_eq!(buffer.snapshot(), "");
+  }
+}
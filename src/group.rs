@@ -0,0 +1,172 @@
+use std::cell::RefCell;
+use tracing::{
+  span,
+  Subscriber,
+};
+use tracing_subscriber::{
+  layer::Context,
+  registry::LookupSpan,
+  Layer,
+};
+
+thread_local! {
+  /// The stack of spans whose `console.group` is currently open, with
+  /// the innermost (most recently entered) group last. WASM is
+  /// single-threaded, so a thread-local stack is enough to track
+  /// actual nesting order.
+  static GROUP_STACK: RefCell<Vec<span::Id>> = RefCell::new(Vec::new());
+}
+
+/// A [`Layer`] that nests events emitted within a span under a
+/// `console.group`, so DevTools shows the nesting/temporal structure
+/// that `tracing` spans carry but the flat
+/// [`ConsoleWriter`](crate::ConsoleWriter) throws away.
+///
+/// `console.group`/`console.groupEnd` are a true LIFO stack maintained
+/// by the browser, so a span that closes out of order (i.e. while
+/// some span entered after it is still open) can't have its group
+/// closed without also closing groups that semantically belong to
+/// those other, still-open spans. Rather than scrambling the visual
+/// nesting, this layer detects that case and simply leaves the group
+/// open, closing it once the spans actually entered after it have
+/// closed and unwound the stack down to it.
+///
+/// # Example
+/// ```rust
+/// use tracing_subscriber::prelude::*;
+/// use tracing_subscriber_wasm::GroupLayer;
+///
+/// tracing_subscriber::registry()
+///   .with(GroupLayer::new())
+///   .init();
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GroupLayer {
+  collapsed: bool,
+}
+
+impl GroupLayer {
+  /// Creates a new [`GroupLayer`] that opens expanded groups.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Opens groups collapsed (`console.groupCollapsed`) instead of
+  /// expanded (`console.group`).
+  pub fn collapsed(mut self) -> Self {
+    self.collapsed = true;
+
+    self
+  }
+
+  /// Removes `id` from `stack`, reporting whether `id` was the
+  /// innermost (last) entry, i.e. whether its `console.group` can be
+  /// closed without disturbing any other still-open group.
+  ///
+  /// If `id` isn't the innermost entry, it's still removed from
+  /// wherever it sits in `stack` so bookkeeping stays consistent, but
+  /// `false` is returned so the caller skips calling
+  /// `console.groupEnd` for it.
+  fn pop_if_innermost(stack: &mut Vec<span::Id>, id: &span::Id) -> bool {
+    match stack.last() {
+      Some(top) if top == id => {
+        stack.pop();
+
+        true
+      }
+      _ => {
+        stack.retain(|open| open != id);
+
+        false
+      }
+    }
+  }
+}
+
+/// Marker stashed in a span's extensions while its `console.group` is
+/// open, so `on_exit` only calls `console.groupEnd` for spans this
+/// layer actually opened a group for.
+struct GroupOpened;
+
+impl<S> Layer<S> for GroupLayer
+where
+  S: Subscriber + for<'a> LookupSpan<'a>,
+{
+  fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(id) else {
+      return;
+    };
+
+    // Entering a span that is already entered and grouped (a nested
+    // enter without an exit in between) should not open a second,
+    // unmatched group.
+    if span.extensions().get::<GroupOpened>().is_some() {
+      return;
+    }
+
+    let label = span.name();
+
+    if self.collapsed {
+      gloo::console::group_collapsed!(label);
+    } else {
+      gloo::console::group!(label);
+    }
+
+    GROUP_STACK.with(|stack| stack.borrow_mut().push(id.clone()));
+
+    span.extensions_mut().insert(GroupOpened);
+  }
+
+  fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(id) else {
+      return;
+    };
+
+    if span.extensions_mut().remove::<GroupOpened>().is_none() {
+      return;
+    }
+
+    let closed =
+      GROUP_STACK.with(|stack| Self::pop_if_innermost(&mut stack.borrow_mut(), id));
+
+    if closed {
+      gloo::console::group_end!();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn innermost_span_closes_its_own_group() {
+    let mut stack = vec![span::Id::from_u64(1), span::Id::from_u64(2)];
+
+    let closed = GroupLayer::pop_if_innermost(&mut stack, &span::Id::from_u64(2));
+
+    assert!(closed);
+    assert_eq!(stack, vec![span::Id::from_u64(1)]);
+  }
+
+  #[test]
+  fn out_of_order_exit_is_skipped_but_still_removed() {
+    let mut stack = vec![span::Id::from_u64(1), span::Id::from_u64(2)];
+
+    // Span 1 closes while span 2 (entered after it) is still open.
+    let closed = GroupLayer::pop_if_innermost(&mut stack, &span::Id::from_u64(1));
+
+    assert!(!closed);
+    assert_eq!(stack, vec![span::Id::from_u64(2)]);
+  }
+
+  #[test]
+  fn exiting_a_span_not_on_the_stack_is_a_no_op() {
+    let mut stack = vec![span::Id::from_u64(1)];
+
+    let closed = GroupLayer::pop_if_innermost(&mut stack, &span::Id::from_u64(2));
+
+    assert!(!closed);
+    assert_eq!(stack, vec![span::Id::from_u64(1)]);
+  }
+}
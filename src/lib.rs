@@ -8,6 +8,31 @@
 //! [`MakeConsoleWriter::map_trace_level_to`] and similar methods when
 //! building the writer.
 //!
+//! [`PerformanceLayer`] is a separate [`tracing_subscriber::Layer`]
+//! that bridges span lifetimes to the browser's User Timing API, so
+//! spans show up on the DevTools Performance timeline.
+//!
+//! [`MakeConsoleWriterExt`] brings in `with_max_level`, `with_min_level`,
+//! `and`, and `or_else` combinators for routing events to different
+//! sinks by level, much like `tracing_subscriber`'s own `MakeWriterExt`.
+//! `with_max_level`/`with_min_level` filter against the event's
+//! original level by default; call `filter_mapped_level` on the result
+//! to filter against the level it's remapped to instead.
+//!
+//! [`StructuredLayer`] logs each event as an expandable object built
+//! from the event's own fields instead of a flat string, and
+//! [`MakeConsoleWriter::with_level_style`] lets you colorize a level's
+//! output using the console's `%c` directive.
+//!
+//! [`GroupLayer`] nests events emitted within a span under a
+//! collapsible `console.group`, so DevTools shows which events
+//! happened inside which span.
+//!
+//! [`MakeBufferedConsoleWriter`] keeps a bounded in-memory
+//! [`LogBuffer`] of formatted lines alongside the usual console
+//! output, so a session's logs can be snapshotted or downloaded for a
+//! bug report.
+//!
 //! ### Important Note
 //! In my testing, if you don't call `.without_time` on the
 //! subscriber builder, a runtime exception will be raised.
@@ -36,6 +61,34 @@ use std::io::{
 use tracing::instrument::WithSubscriber;
 use tracing_subscriber::fmt::MakeWriter;
 
+mod buffered;
+mod group;
+mod performance;
+mod structured;
+mod writer_ext;
+
+pub use buffered::{
+  LogBuffer,
+  MakeBufferedConsoleWriter,
+  MakeRingBufferWriter,
+  RingBufferWriter,
+};
+pub use group::GroupLayer;
+pub use performance::PerformanceLayer;
+pub use structured::StructuredLayer;
+pub use writer_ext::{
+  EitherWriter,
+  MakeConsoleWriterExt,
+  MaybeEnabled,
+  MaybeWriter,
+  NoWriter,
+  OrElse,
+  Tee,
+  TeeWriter,
+  WithMaxLevel,
+  WithMinLevel,
+};
+
 /// This is the main type that is passed to `with_writer`.
 ///
 /// Note that you can map the tracing levels to different console
@@ -44,8 +97,11 @@ use tracing_subscriber::fmt::MakeWriter;
 /// such as `debug`, to avoid all `trace` tracing events
 /// showing up in the browser's console with their backtrace
 /// expanded.
-#[derive(Clone, Copy, Debug, Default)]
-pub struct MakeConsoleWriter(MappedLevels);
+#[derive(Clone, Debug, Default)]
+pub struct MakeConsoleWriter {
+  mapped_levels: MappedLevels,
+  level_styles: LevelStyles,
+}
 
 impl From<MappedLevels> for MakeConsoleWriter {
   fn from(mapped_levels: MappedLevels) -> Self {
@@ -61,40 +117,67 @@ impl MakeConsoleWriter {
 
   /// Creates a new [`MakeConsoleWriter`] with a custom [`MappedLevels`].
   pub fn from_mapped_levels(mapped_levels: MappedLevels) -> Self {
-    Self(mapped_levels)
+    Self {
+      mapped_levels,
+      ..Self::default()
+    }
   }
 
   /// Maps the [`tracing::Level::TRACE`] to another console level.
   pub fn map_trace_level_to(mut self, level: tracing::Level) -> Self {
-    self.0.trace = level;
+    self.mapped_levels.trace = level;
 
     self
   }
 
   /// Maps the [`tracing::Level::DEBUG`] to another console level.
   pub fn map_debug_level_to(mut self, level: tracing::Level) -> Self {
-    self.0.debug = level;
+    self.mapped_levels.debug = level;
 
     self
   }
 
   /// Maps the [`tracing::Level::INFO`] to another console level.
   pub fn map_info_level_to(mut self, level: tracing::Level) -> Self {
-    self.0.info = level;
+    self.mapped_levels.info = level;
 
     self
   }
 
   /// Maps the [`tracing::Level::WARN`] to another console level.
   pub fn map_warn_level_to(mut self, level: tracing::Level) -> Self {
-    self.0.warn = level;
+    self.mapped_levels.warn = level;
 
     self
   }
 
   /// Maps the [`tracing::Level::ERROR`] to another console level.
   pub fn map_error_level_to(mut self, level: tracing::Level) -> Self {
-    self.0.error = level;
+    self.mapped_levels.error = level;
+
+    self
+  }
+
+  /// Returns a copy of the [`MappedLevels`] currently configured on
+  /// this writer.
+  pub fn mapped_levels(&self) -> MappedLevels {
+    self.mapped_levels
+  }
+
+  /// Sets the CSS `style` applied to `level` events using the
+  /// console's `%c` format directive, e.g.
+  /// `with_level_style(Level::ERROR, "color:red;font-weight:bold")`.
+  ///
+  /// For structured, expandable object output instead of styled flat
+  /// text, add [`StructuredLayer`] to the subscriber rather than
+  /// configuring this writer, since the console can't style an
+  /// object the way it can a `%c`-prefixed string.
+  pub fn with_level_style(
+    mut self,
+    level: tracing::Level,
+    style: impl Into<String>,
+  ) -> Self {
+    self.level_styles.set(level, style.into());
 
     self
   }
@@ -108,7 +191,13 @@ impl<'a> MakeWriter<'a> for MakeConsoleWriter {
   }
 
   fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
-    ConsoleWriter(*meta.level(), Vec::with_capacity(256))
+    let level = *meta.level();
+
+    ConsoleWriter {
+      console_level: self.mapped_levels.get(level),
+      buf: Vec::with_capacity(256),
+      style: self.level_styles.get(level).cloned(),
+    }
   }
 }
 
@@ -145,29 +234,108 @@ impl Default for MappedLevels {
   }
 }
 
+impl MappedLevels {
+  /// Returns the console level `level` is mapped to.
+  pub fn get(&self, level: tracing::Level) -> tracing::Level {
+    use tracing::Level;
+
+    match level {
+      Level::TRACE => self.trace,
+      Level::DEBUG => self.debug,
+      Level::INFO => self.info,
+      Level::WARN => self.warn,
+      Level::ERROR => self.error,
+    }
+  }
+}
+
+/// Optional per-[`tracing::Level`] CSS, applied to console output via
+/// the `%c` format directive when
+/// [`MakeConsoleWriter::with_level_style`] is used.
+#[derive(Clone, Debug, Default)]
+pub struct LevelStyles {
+  /// The CSS applied to [`tracing::Level::TRACE`] events.
+  pub trace: Option<String>,
+  /// The CSS applied to [`tracing::Level::DEBUG`] events.
+  pub debug: Option<String>,
+  /// The CSS applied to [`tracing::Level::INFO`] events.
+  pub info: Option<String>,
+  /// The CSS applied to [`tracing::Level::WARN`] events.
+  pub warn: Option<String>,
+  /// The CSS applied to [`tracing::Level::ERROR`] events.
+  pub error: Option<String>,
+}
+
+impl LevelStyles {
+  fn get(&self, level: tracing::Level) -> Option<&String> {
+    use tracing::Level;
+
+    match level {
+      Level::TRACE => self.trace.as_ref(),
+      Level::DEBUG => self.debug.as_ref(),
+      Level::INFO => self.info.as_ref(),
+      Level::WARN => self.warn.as_ref(),
+      Level::ERROR => self.error.as_ref(),
+    }
+  }
+
+  fn set(&mut self, level: tracing::Level, style: String) {
+    use tracing::Level;
+
+    let slot = match level {
+      Level::TRACE => &mut self.trace,
+      Level::DEBUG => &mut self.debug,
+      Level::INFO => &mut self.info,
+      Level::WARN => &mut self.warn,
+      Level::ERROR => &mut self.error,
+    };
+
+    *slot = Some(style);
+  }
+}
+
 /// The type which is responsible for actually writing the tracing
 /// event out to the console.
-pub struct ConsoleWriter(tracing::Level, Vec<u8>);
+pub struct ConsoleWriter {
+  /// The console level this event is dispatched to, i.e. the event's
+  /// original [`tracing::Level`] already passed through
+  /// [`MappedLevels`].
+  console_level: tracing::Level,
+  buf: Vec<u8>,
+  style: Option<String>,
+}
 
 impl io::Write for ConsoleWriter {
   fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-    self.1.write(buf)
+    self.buf.write(buf)
   }
 
   fn flush(&mut self) -> io::Result<()> {
     use gloo::console;
     use tracing::Level;
 
-    let data = std::str::from_utf8(&self.1).map_err(|_| {
+    let data = std::str::from_utf8(&self.buf).map_err(|_| {
       io::Error::new(io::ErrorKind::InvalidData, "data not UTF-8")
     })?;
 
-    match self.0 {
-      Level::TRACE => console::debug!(data),
-      Level::DEBUG => console::debug!(data),
-      Level::INFO => console::log!(data),
-      Level::WARN => console::warn!(data),
-      Level::ERROR => console::error!(data),
+    match &self.style {
+      Some(style) => {
+        let styled = format!("%c{data}");
+
+        match self.console_level {
+          Level::TRACE | Level::DEBUG => console::debug!(styled, style),
+          Level::INFO => console::log!(styled, style),
+          Level::WARN => console::warn!(styled, style),
+          Level::ERROR => console::error!(styled, style),
+        }
+      }
+      None => match self.console_level {
+        Level::TRACE => console::debug!(data),
+        Level::DEBUG => console::debug!(data),
+        Level::INFO => console::log!(data),
+        Level::WARN => console::warn!(data),
+        Level::ERROR => console::error!(data),
+      },
     }
 
     Ok(())
@@ -0,0 +1,273 @@
+use std::cell::Cell;
+use tracing::{
+  field::{
+    Field,
+    Visit,
+  },
+  span,
+  Subscriber,
+};
+use tracing_subscriber::{
+  layer::Context,
+  registry::LookupSpan,
+  Layer,
+};
+
+/// A [`Layer`] which bridges `tracing` span lifetimes to the browser's
+/// [User Timing API][user-timing], so spans show up as labeled entries
+/// on the DevTools Performance timeline.
+///
+/// On span entry a `performance.mark` is recorded, and on span close a
+/// matching `performance.measure` is created between the entry and exit
+/// marks. If `window.performance()` is unavailable (for example, inside
+/// a worker without it), every call is a silent no-op.
+///
+/// [user-timing]: https://developer.mozilla.org/en-US/docs/Web/API/User_Timing_API
+///
+/// # Example
+/// ```rust
+/// use tracing_subscriber::prelude::*;
+/// use tracing_subscriber_wasm::PerformanceLayer;
+///
+/// tracing_subscriber::registry()
+///   .with(PerformanceLayer::new())
+///   .init();
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PerformanceLayer {
+  include_fields_in_detail: bool,
+}
+
+impl PerformanceLayer {
+  /// Creates a new [`PerformanceLayer`].
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Appends the span's fields, as recorded when the span was created,
+  /// to the name of the recorded `performance.measure` (e.g.
+  /// `my_span {a=1, b="two"}`), so they show up alongside the span in
+  /// the DevTools Performance panel. The measure's `detail` is not set.
+  pub fn with_fields_in_detail(mut self, include: bool) -> Self {
+    self.include_fields_in_detail = include;
+
+    self
+  }
+
+  fn start_mark_name(id: &span::Id, name: &str) -> String {
+    format!("tracing-subscriber-wasm/{name}/{:?}/start", id)
+  }
+
+  fn end_mark_name(id: &span::Id, name: &str) -> String {
+    format!("tracing-subscriber-wasm/{name}/{:?}/end", id)
+  }
+
+  /// Returns the mark name to record for the `entry`-th time (0-based)
+  /// a span is entered. The very first entry reuses `start_mark`
+  /// itself, since that's the name `on_close` measures from; every
+  /// later re-entry (e.g. an `Instrument`ed future polled across
+  /// multiple `.await` points) gets its own suffixed name instead of
+  /// overwriting it, since the User Timing API resolves a mark name
+  /// to its *last* matching entry.
+  fn entry_mark_name(start_mark: &str, entry: usize) -> String {
+    if entry == 0 {
+      start_mark.to_string()
+    } else {
+      format!("{start_mark}/re-enter-{entry}")
+    }
+  }
+}
+
+/// Bookkeeping stashed in the span's extensions between
+/// `on_new_span`/`on_enter` and `on_close`.
+#[derive(Debug)]
+struct SpanMarks {
+  start_mark: String,
+  name: &'static str,
+  fields: String,
+  /// How many times this span has been entered so far, used to give
+  /// each re-entry's mark a unique name instead of overwriting
+  /// `start_mark`.
+  entry_count: Cell<usize>,
+}
+
+/// Renders a span's fields as a `key=value, ...` string for inclusion
+/// in a `performance.measure`'s name.
+#[derive(Default)]
+struct FieldsToString(String);
+
+impl Visit for FieldsToString {
+  fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+    if !self.0.is_empty() {
+      self.0.push_str(", ");
+    }
+
+    use std::fmt::Write;
+    let _ = write!(self.0, "{}={:?}", field.name(), value);
+  }
+}
+
+fn performance() -> Option<web_sys::Performance> {
+  web_sys::window().and_then(|window| window.performance())
+}
+
+impl<S> Layer<S> for PerformanceLayer
+where
+  S: Subscriber + for<'a> LookupSpan<'a>,
+{
+  fn on_new_span(
+    &self,
+    attrs: &span::Attributes<'_>,
+    id: &span::Id,
+    ctx: Context<'_, S>,
+  ) {
+    let Some(span) = ctx.span(id) else {
+      return;
+    };
+
+    let fields = if self.include_fields_in_detail {
+      let mut visitor = FieldsToString::default();
+      attrs.record(&mut visitor);
+      visitor.0
+    } else {
+      String::new()
+    };
+
+    span.extensions_mut().insert(SpanMarks {
+      start_mark: Self::start_mark_name(id, span.name()),
+      name: span.name(),
+      fields,
+      entry_count: Cell::new(0),
+    });
+  }
+
+  fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(id) else {
+      return;
+    };
+
+    let Some(performance) = performance() else {
+      return;
+    };
+
+    if let Some(marks) = span.extensions().get::<SpanMarks>() {
+      let entry = marks.entry_count.get();
+      marks.entry_count.set(entry + 1);
+
+      let mark = Self::entry_mark_name(&marks.start_mark, entry);
+
+      let _ = performance.mark(&mark);
+    }
+  }
+
+  fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(&id) else {
+      return;
+    };
+
+    let Some(marks) = span.extensions_mut().remove::<SpanMarks>() else {
+      return;
+    };
+
+    let Some(performance) = performance() else {
+      return;
+    };
+
+    let end_mark = Self::end_mark_name(&id, marks.name);
+
+    let _ = performance.mark(&end_mark);
+
+    let measure_name = if marks.fields.is_empty() {
+      marks.name.to_string()
+    } else {
+      format!("{} {{{}}}", marks.name, marks.fields)
+    };
+
+    let _ = performance.measure_with_start_mark_and_end_mark(
+      &measure_name,
+      &marks.start_mark,
+      &end_mark,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn start_and_end_mark_names_are_distinct_and_stable() {
+    let id = span::Id::from_u64(1);
+
+    assert_ne!(
+      PerformanceLayer::start_mark_name(&id, "my_span"),
+      PerformanceLayer::end_mark_name(&id, "my_span"),
+    );
+    assert_eq!(
+      PerformanceLayer::start_mark_name(&id, "my_span"),
+      PerformanceLayer::start_mark_name(&id, "my_span"),
+    );
+  }
+
+  #[test]
+  fn first_entry_reuses_the_start_mark_unchanged() {
+    let start_mark = "tracing-subscriber-wasm/my_span/Id(1)/start".to_string();
+
+    assert_eq!(
+      PerformanceLayer::entry_mark_name(&start_mark, 0),
+      start_mark,
+    );
+  }
+
+  #[test]
+  fn later_entries_get_unique_suffixed_mark_names() {
+    let start_mark = "tracing-subscriber-wasm/my_span/Id(1)/start".to_string();
+
+    let second = PerformanceLayer::entry_mark_name(&start_mark, 1);
+    let third = PerformanceLayer::entry_mark_name(&start_mark, 2);
+
+    assert_ne!(second, start_mark);
+    assert_ne!(third, start_mark);
+    assert_ne!(second, third);
+  }
+
+  #[test]
+  fn fields_to_string_joins_fields_with_commas() {
+    let mut visitor = FieldsToString::default();
+
+    visitor.record_debug(&field_named("a"), &1);
+    visitor.record_debug(&field_named("b"), &"two");
+
+    assert_eq!(visitor.0, "a=1, b=\"two\"");
+  }
+
+  /// Builds a standalone [`Field`] for use in tests, since [`Field`]
+  /// has no public constructor of its own.
+  fn field_named(name: &'static str) -> Field {
+    use tracing::{
+      callsite::{
+        Callsite,
+        Identifier,
+      },
+      field::FieldSet,
+      Metadata,
+    };
+
+    struct NoopCallsite;
+
+    impl Callsite for NoopCallsite {
+      fn set_interest(&self, _interest: tracing::subscriber::Interest) {}
+
+      fn metadata(&self) -> &Metadata<'_> {
+        unreachable!("not used by this test")
+      }
+    }
+
+    static CALLSITE: NoopCallsite = NoopCallsite;
+
+    let field_names: &'static [&'static str] = &["a", "b"];
+    let fields = FieldSet::new(field_names, Identifier(&CALLSITE));
+
+    fields.field(name).expect("field exists in field set")
+  }
+}
@@ -0,0 +1,130 @@
+use tracing::{
+  field::{
+    Field,
+    Visit,
+  },
+  Event,
+  Subscriber,
+};
+use tracing_subscriber::{
+  layer::Context,
+  registry::LookupSpan,
+  Layer,
+};
+
+/// A [`Layer`] that logs each event to the console as an expandable
+/// object built from the event's own fields, instead of a flat string.
+///
+/// Unlike [`MakeConsoleWriter`](crate::MakeConsoleWriter), which only
+/// ever sees a formatted, already-rendered event, this hooks `on_event`
+/// directly so it can visit the event's fields and build a real
+/// `console.log`-able object out of them.
+///
+/// # Example
+/// ```rust
+/// use tracing_subscriber::prelude::*;
+/// use tracing_subscriber_wasm::StructuredLayer;
+///
+/// tracing_subscriber::registry()
+///   .with(StructuredLayer::new())
+///   .init();
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StructuredLayer {
+  mapped_levels: crate::MappedLevels,
+}
+
+impl StructuredLayer {
+  /// Creates a new [`StructuredLayer`].
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the console level `level` events are dispatched to, just
+  /// like [`MakeConsoleWriter`](crate::MakeConsoleWriter)'s
+  /// `map_trace_level_to` and friends.
+  pub fn with_mapped_levels(mut self, mapped_levels: crate::MappedLevels) -> Self {
+    self.mapped_levels = mapped_levels;
+
+    self
+  }
+}
+
+/// Captures an event's fields into a `js_sys::Object`, mirroring
+/// [`performance::FieldsToString`](crate::performance), but building a
+/// real JS value per field instead of a single formatted string.
+#[derive(Default)]
+struct FieldsToObject(js_sys::Object);
+
+impl Visit for FieldsToObject {
+  fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+    let _ = js_sys::Reflect::set(
+      &self.0,
+      &wasm_bindgen::JsValue::from_str(field.name()),
+      &wasm_bindgen::JsValue::from_str(&format!("{:?}", value)),
+    );
+  }
+
+  fn record_str(&mut self, field: &Field, value: &str) {
+    let _ = js_sys::Reflect::set(
+      &self.0,
+      &wasm_bindgen::JsValue::from_str(field.name()),
+      &wasm_bindgen::JsValue::from_str(value),
+    );
+  }
+
+  fn record_bool(&mut self, field: &Field, value: bool) {
+    let _ = js_sys::Reflect::set(
+      &self.0,
+      &wasm_bindgen::JsValue::from_str(field.name()),
+      &wasm_bindgen::JsValue::from_bool(value),
+    );
+  }
+
+  fn record_i64(&mut self, field: &Field, value: i64) {
+    let _ = js_sys::Reflect::set(
+      &self.0,
+      &wasm_bindgen::JsValue::from_str(field.name()),
+      &wasm_bindgen::JsValue::from_f64(value as f64),
+    );
+  }
+
+  fn record_u64(&mut self, field: &Field, value: u64) {
+    let _ = js_sys::Reflect::set(
+      &self.0,
+      &wasm_bindgen::JsValue::from_str(field.name()),
+      &wasm_bindgen::JsValue::from_f64(value as f64),
+    );
+  }
+
+  fn record_f64(&mut self, field: &Field, value: f64) {
+    let _ = js_sys::Reflect::set(
+      &self.0,
+      &wasm_bindgen::JsValue::from_str(field.name()),
+      &wasm_bindgen::JsValue::from_f64(value),
+    );
+  }
+}
+
+impl<S> Layer<S> for StructuredLayer
+where
+  S: Subscriber + for<'a> LookupSpan<'a>,
+{
+  fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+    use gloo::console;
+    use tracing::Level;
+
+    let mut visitor = FieldsToObject::default();
+    event.record(&mut visitor);
+
+    let value: wasm_bindgen::JsValue = visitor.0.into();
+    let console_level = self.mapped_levels.get(*event.metadata().level());
+
+    match console_level {
+      Level::TRACE | Level::DEBUG => console::debug!(value),
+      Level::INFO => console::log!(value),
+      Level::WARN => console::warn!(value),
+      Level::ERROR => console::error!(value),
+    }
+  }
+}
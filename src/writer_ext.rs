@@ -0,0 +1,489 @@
+use std::io;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Extension trait adding composable level-routing combinators on top
+/// of [`MakeWriter`], mirroring `tracing-subscriber`'s own
+/// `MakeWriterExt`.
+///
+/// These let a console [`MakeWriter`] be restricted to a level range,
+/// or combined with another sink, without writing a bespoke
+/// [`MakeWriter`] impl by hand.
+pub trait MakeConsoleWriterExt<'a>: MakeWriter<'a> + Sized {
+  /// Wraps `self` so it is only used for events at or more severe
+  /// than `level` (i.e. `event_level <= level`).
+  ///
+  /// By default this is checked against the event's *original*
+  /// [`tracing::Level`], before any [`MappedLevels`](crate::MappedLevels)
+  /// remapping is applied by [`MakeConsoleWriter`](crate::MakeConsoleWriter).
+  /// When wrapping a [`MakeConsoleWriter`](crate::MakeConsoleWriter)
+  /// directly, call
+  /// [`filter_mapped_level`](WithMaxLevel::filter_mapped_level) on the
+  /// result to filter against the remapped level instead.
+  fn with_max_level(self, level: tracing::Level) -> WithMaxLevel<Self> {
+    WithMaxLevel {
+      make_writer: self,
+      level,
+      source: LevelSource::Original,
+    }
+  }
+
+  /// Wraps `self` so it is only used for events at or less severe
+  /// than `level` (i.e. `event_level >= level`).
+  ///
+  /// As with [`with_max_level`](MakeConsoleWriterExt::with_max_level),
+  /// this is checked against the event's original level by default;
+  /// call [`filter_mapped_level`](WithMinLevel::filter_mapped_level)
+  /// to filter against the remapped level instead.
+  fn with_min_level(self, level: tracing::Level) -> WithMinLevel<Self> {
+    WithMinLevel {
+      make_writer: self,
+      level,
+      source: LevelSource::Original,
+    }
+  }
+
+  /// Combines `self` with `other`, returning a [`MakeWriter`] that
+  /// writes every event to both.
+  fn and<B>(self, other: B) -> Tee<Self, B>
+  where
+    B: MakeWriter<'a>,
+  {
+    Tee {
+      a: self,
+      b: other,
+    }
+  }
+
+  /// Combines `self` with `other`, falling back to `other` whenever
+  /// `self` declines an event, for example because it was wrapped in
+  /// [`with_max_level`](MakeConsoleWriterExt::with_max_level) or
+  /// [`with_min_level`](MakeConsoleWriterExt::with_min_level) and the
+  /// event's level is out of range.
+  fn or_else<B>(self, other: B) -> OrElse<Self, B>
+  where
+    B: MakeWriter<'a>,
+    Self::Writer: MaybeEnabled,
+  {
+    OrElse {
+      primary: self,
+      secondary: other,
+    }
+  }
+}
+
+impl<'a, M> MakeConsoleWriterExt<'a> for M where M: MakeWriter<'a> {}
+
+/// Reports whether a writer produced by [`MakeWriter::make_writer_for`]
+/// actually wants the event it was created for, letting [`OrElse`]
+/// decide whether to fall back to its secondary [`MakeWriter`].
+///
+/// Plain writers (that never decline an event) are always enabled;
+/// only [`WithMaxLevel`]/[`WithMinLevel`]'s writer can report
+/// otherwise.
+pub trait MaybeEnabled {
+  /// Returns `true` if this writer wants the event it was made for.
+  fn is_enabled(&self) -> bool;
+}
+
+impl MaybeEnabled for crate::ConsoleWriter {
+  fn is_enabled(&self) -> bool {
+    true
+  }
+}
+
+/// Which [`tracing::Level`] a [`WithMaxLevel`]/[`WithMinLevel`] should
+/// filter an event against.
+#[derive(Clone, Copy, Debug)]
+enum LevelSource {
+  /// Filter against the event's original [`tracing::Level`].
+  Original,
+  /// Filter against the level the event would be mapped to in the
+  /// console, per a snapshot of [`MappedLevels`](crate::MappedLevels)
+  /// taken when `filter_mapped_level` was called.
+  Mapped(crate::MappedLevels),
+}
+
+impl LevelSource {
+  fn resolve(&self, meta: &tracing::Metadata<'_>) -> tracing::Level {
+    self.resolve_level(*meta.level())
+  }
+
+  fn resolve_level(&self, level: tracing::Level) -> tracing::Level {
+    match self {
+      Self::Original => level,
+      Self::Mapped(mapped_levels) => mapped_levels.get(level),
+    }
+  }
+}
+
+/// A [`MakeWriter`] that only writes for events at or above a given
+/// severity (i.e. `level` or more severe). Returned by
+/// [`MakeConsoleWriterExt::with_max_level`].
+#[derive(Clone, Copy, Debug)]
+pub struct WithMaxLevel<M> {
+  make_writer: M,
+  level: tracing::Level,
+  source: LevelSource,
+}
+
+impl<M> WithMaxLevel<M> {
+  fn enabled_for(&self, meta: &tracing::Metadata<'_>) -> bool {
+    self.source.resolve(meta) <= self.level
+  }
+}
+
+impl WithMaxLevel<crate::MakeConsoleWriter> {
+  /// Filters against the level the event would be mapped to in the
+  /// console (via the wrapped [`MakeConsoleWriter`](crate::MakeConsoleWriter)'s
+  /// [`MappedLevels`](crate::MappedLevels)), instead of the event's
+  /// original level.
+  pub fn filter_mapped_level(mut self) -> Self {
+    self.source = LevelSource::Mapped(self.make_writer.mapped_levels());
+
+    self
+  }
+}
+
+/// A [`MakeWriter`] that only writes for events at or below a given
+/// severity (i.e. `level` or less severe). Returned by
+/// [`MakeConsoleWriterExt::with_min_level`].
+#[derive(Clone, Copy, Debug)]
+pub struct WithMinLevel<M> {
+  make_writer: M,
+  level: tracing::Level,
+  source: LevelSource,
+}
+
+impl<M> WithMinLevel<M> {
+  fn enabled_for(&self, meta: &tracing::Metadata<'_>) -> bool {
+    self.source.resolve(meta) >= self.level
+  }
+}
+
+impl WithMinLevel<crate::MakeConsoleWriter> {
+  /// Filters against the level the event would be mapped to in the
+  /// console (via the wrapped [`MakeConsoleWriter`](crate::MakeConsoleWriter)'s
+  /// [`MappedLevels`](crate::MappedLevels)), instead of the event's
+  /// original level.
+  pub fn filter_mapped_level(mut self) -> Self {
+    self.source = LevelSource::Mapped(self.make_writer.mapped_levels());
+
+    self
+  }
+}
+
+/// A [`MakeWriter`] that forwards every write to both of its inner
+/// writers. Returned by [`MakeConsoleWriterExt::and`].
+#[derive(Clone, Copy, Debug)]
+pub struct Tee<A, B> {
+  a: A,
+  b: B,
+}
+
+/// A [`MakeWriter`] that falls back to a secondary [`MakeWriter`]
+/// whenever the primary one declines an event. Returned by
+/// [`MakeConsoleWriterExt::or_else`].
+#[derive(Clone, Copy, Debug)]
+pub struct OrElse<A, B> {
+  primary: A,
+  secondary: B,
+}
+
+/// A [`MakeWriter::Writer`] that discards everything written to it.
+///
+/// Used by [`WithMaxLevel`]/[`WithMinLevel`] to stand in for "no
+/// writer" when an event's level is out of range, since
+/// [`MakeWriter::make_writer_for`] must return a concrete writer.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoWriter;
+
+impl io::Write for NoWriter {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+/// The [`MakeWriter::Writer`] produced by [`WithMaxLevel`] and
+/// [`WithMinLevel`]: either the inner writer, or [`NoWriter`] when
+/// the event's level was out of range.
+#[derive(Debug)]
+pub enum MaybeWriter<W> {
+  /// The inner [`MakeWriter`]'s writer, used when the event's level
+  /// is in range.
+  Some(W),
+  /// A no-op writer, used when the event's level is out of range.
+  None(NoWriter),
+}
+
+impl<W> io::Write for MaybeWriter<W>
+where
+  W: io::Write,
+{
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    match self {
+      Self::Some(writer) => writer.write(buf),
+      Self::None(writer) => writer.write(buf),
+    }
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    match self {
+      Self::Some(writer) => writer.flush(),
+      Self::None(writer) => writer.flush(),
+    }
+  }
+}
+
+impl<W> MaybeEnabled for MaybeWriter<W> {
+  fn is_enabled(&self) -> bool {
+    matches!(self, Self::Some(_))
+  }
+}
+
+impl<'a, M> MakeWriter<'a> for WithMaxLevel<M>
+where
+  M: MakeWriter<'a>,
+{
+  type Writer = MaybeWriter<M::Writer>;
+
+  fn make_writer(&'a self) -> Self::Writer {
+    MaybeWriter::Some(self.make_writer.make_writer())
+  }
+
+  fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+    if self.enabled_for(meta) {
+      MaybeWriter::Some(self.make_writer.make_writer_for(meta))
+    } else {
+      MaybeWriter::None(NoWriter)
+    }
+  }
+}
+
+impl<'a, M> MakeWriter<'a> for WithMinLevel<M>
+where
+  M: MakeWriter<'a>,
+{
+  type Writer = MaybeWriter<M::Writer>;
+
+  fn make_writer(&'a self) -> Self::Writer {
+    MaybeWriter::Some(self.make_writer.make_writer())
+  }
+
+  fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+    if self.enabled_for(meta) {
+      MaybeWriter::Some(self.make_writer.make_writer_for(meta))
+    } else {
+      MaybeWriter::None(NoWriter)
+    }
+  }
+}
+
+impl<'a, A, B> MakeWriter<'a> for Tee<A, B>
+where
+  A: MakeWriter<'a>,
+  B: MakeWriter<'a>,
+{
+  type Writer = TeeWriter<A::Writer, B::Writer>;
+
+  fn make_writer(&'a self) -> Self::Writer {
+    TeeWriter(self.a.make_writer(), self.b.make_writer())
+  }
+
+  fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+    TeeWriter(
+      self.a.make_writer_for(meta),
+      self.b.make_writer_for(meta),
+    )
+  }
+}
+
+/// The [`MakeWriter::Writer`] produced by [`Tee`], forwarding every
+/// write to both inner writers.
+#[derive(Debug)]
+pub struct TeeWriter<A, B>(A, B);
+
+impl<A, B> io::Write for TeeWriter<A, B>
+where
+  A: io::Write,
+  B: io::Write,
+{
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let a = self.0.write(buf)?;
+    let b = self.1.write(buf)?;
+
+    Ok(a.min(b))
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.0.flush()?;
+    self.1.flush()
+  }
+}
+
+impl<A, B> MaybeEnabled for TeeWriter<A, B> {
+  fn is_enabled(&self) -> bool {
+    true
+  }
+}
+
+impl<'a, A, B> MakeWriter<'a> for OrElse<A, B>
+where
+  A: MakeWriter<'a>,
+  A::Writer: MaybeEnabled,
+  B: MakeWriter<'a>,
+{
+  type Writer = EitherWriter<A::Writer, B::Writer>;
+
+  fn make_writer(&'a self) -> Self::Writer {
+    let primary = self.primary.make_writer();
+
+    if primary.is_enabled() {
+      EitherWriter::A(primary)
+    } else {
+      EitherWriter::B(self.secondary.make_writer())
+    }
+  }
+
+  fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+    let primary = self.primary.make_writer_for(meta);
+
+    if primary.is_enabled() {
+      EitherWriter::A(primary)
+    } else {
+      EitherWriter::B(self.secondary.make_writer_for(meta))
+    }
+  }
+}
+
+/// The [`MakeWriter::Writer`] produced by [`OrElse`]: either the
+/// primary's writer, or the secondary's when the primary declined.
+#[derive(Debug)]
+pub enum EitherWriter<A, B> {
+  /// The primary [`MakeWriter`]'s writer.
+  A(A),
+  /// The secondary [`MakeWriter`]'s writer, used when the primary
+  /// declined.
+  B(B),
+}
+
+impl<A, B> io::Write for EitherWriter<A, B>
+where
+  A: io::Write,
+  B: io::Write,
+{
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    match self {
+      Self::A(writer) => writer.write(buf),
+      Self::B(writer) => writer.write(buf),
+    }
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    match self {
+      Self::A(writer) => writer.flush(),
+      Self::B(writer) => writer.flush(),
+    }
+  }
+}
+
+impl<A, B> MaybeEnabled for EitherWriter<A, B>
+where
+  A: MaybeEnabled,
+  B: MaybeEnabled,
+{
+  fn is_enabled(&self) -> bool {
+    match self {
+      Self::A(writer) => writer.is_enabled(),
+      Self::B(writer) => writer.is_enabled(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::io::Write as _;
+  use tracing::Level;
+
+  #[test]
+  fn level_source_original_ignores_mapping() {
+    let source = LevelSource::Original;
+
+    assert_eq!(source.resolve_level(Level::TRACE), Level::TRACE);
+    assert_eq!(source.resolve_level(Level::ERROR), Level::ERROR);
+  }
+
+  #[test]
+  fn level_source_mapped_applies_mapping() {
+    let mapped_levels = crate::MakeConsoleWriter::default()
+      .map_trace_level_to(Level::DEBUG)
+      .mapped_levels();
+    let source = LevelSource::Mapped(mapped_levels);
+
+    assert_eq!(source.resolve_level(Level::TRACE), Level::DEBUG);
+    assert_eq!(source.resolve_level(Level::ERROR), Level::ERROR);
+  }
+
+  #[test]
+  fn maybe_writer_reports_enabled_by_variant() {
+    assert!(MaybeWriter::Some(NoWriter).is_enabled());
+    assert!(!MaybeWriter::<NoWriter>::None(NoWriter).is_enabled());
+  }
+
+  #[derive(Clone, Default)]
+  struct VecWriter(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+  impl io::Write for VecWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+      self.0.borrow_mut().extend_from_slice(buf);
+
+      Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn tee_writer_forwards_to_both_inner_writers() {
+    let a = VecWriter::default();
+    let b = VecWriter::default();
+    let mut tee = TeeWriter(a.clone(), b.clone());
+
+    tee.write_all(b"hello").unwrap();
+
+    assert_eq!(&*a.0.borrow(), b"hello");
+    assert_eq!(&*b.0.borrow(), b"hello");
+  }
+
+  #[test]
+  fn either_writer_forwards_to_the_active_variant() {
+    let a = VecWriter::default();
+    let b = VecWriter::default();
+
+    let mut active_a = EitherWriter::A(a.clone());
+    active_a.write_all(b"a").unwrap();
+
+    let mut active_b: EitherWriter<VecWriter, VecWriter> = EitherWriter::B(b.clone());
+    active_b.write_all(b"b").unwrap();
+
+    assert_eq!(&*a.0.borrow(), b"a");
+    assert_eq!(&*b.0.borrow(), b"b");
+  }
+
+  #[test]
+  fn either_writer_is_enabled_delegates_to_the_active_variant() {
+    let enabled: EitherWriter<MaybeWriter<NoWriter>, MaybeWriter<NoWriter>> =
+      EitherWriter::A(MaybeWriter::Some(NoWriter));
+    let disabled: EitherWriter<MaybeWriter<NoWriter>, MaybeWriter<NoWriter>> =
+      EitherWriter::A(MaybeWriter::None(NoWriter));
+
+    assert!(enabled.is_enabled());
+    assert!(!disabled.is_enabled());
+  }
+}